@@ -3,30 +3,36 @@
 #![feature(type_ascription)]
 #![feature(conservative_impl_trait)]
 
-// Questions
-// Can we impl Iterator for Scanner
-
-// TODO
-//   delimited scanning
-//   non-line-broken scanning
-
 // References
 // https://doc.rust-lang.org/nightly/std/fmt/index.html
 // https://docs.oracle.com/javase/7/docs/api/java/util/Scanner.html
 // https://en.wikipedia.org/wiki/Scanf_format_string
 // https://github.com/DanielKeep/rust-scan
 
+extern crate serde;
+#[cfg(test)]
+#[macro_use]
+extern crate serde_derive;
+
 use std::cmp::min;
-use std::io::{Read, BufReader, BufRead};
+use std::fmt;
+use std::io::{Read, BufReader, BufRead, Seek, SeekFrom};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
 use std::str::pattern::Pattern;
 use std::str::FromStr;
 
-// TODO Serde
-pub trait Deserialize {}
+use serde::de::{self, DeserializeOwned, Visitor};
 
 pub trait Scanner {
     fn expect<'a, P: Pattern<'a>>(&'a mut self, p: P) -> Result<usize, String>;
 
+    // Whether `next` occurs anywhere in the rest of the current line, without
+    // consuming anything. Lets callers that try a pattern-bounded scan tell
+    // "the pattern isn't there" apart from "it's there, but the token before
+    // it didn't parse" before committing to a fallback.
+    fn contains<'a, P: Pattern<'a>>(&'a mut self, next: P) -> bool;
+
     fn has_next(&mut self) -> bool;
     // Err case is always empty string
     fn next(&mut self) -> Result<char, String>;
@@ -40,8 +46,138 @@ pub trait Scanner {
     fn scan<T: FromStr>(&mut self) -> Result<T, String>;
     fn scan_to<'a, T: FromStr, P: Pattern<'a>>(&'a mut self, next: P) -> Result<T, String>;
 
-    fn scan_de<T: Deserialize>(&mut self) -> Result<T, String> { unimplemented!(); }
-    fn scan_de_to<'a, T: Deserialize, P: Pattern<'a>>(&'a mut self, _next: P) -> Result<T, String> { unimplemented!(); }
+    // Like scan_to, but consumes to the end of the current line instead of
+    // erroring when `next` doesn't occur.
+    fn scan_to_or_end<'a, T: FromStr, P: Pattern<'a>>(&'a mut self, next: P) -> Result<T, String>;
+    fn scan_str_to_or_end<'a, P: Pattern<'a>>(&'a mut self, result: &mut str, next: P) -> Result<usize, String>;
+
+    /// Sets a default delimiter, mirroring Java Scanner's `useDelimiter`, so that
+    /// `scan_delimited` doesn't need a pattern passed to it on every call.
+    fn use_delimiter<D: Delimiter>(&mut self, delim: D);
+
+    /// Scans a single token using the delimiter set by `use_delimiter`, consuming
+    /// to the end of the current line if no delimiter has been configured, or if
+    /// the configured delimiter doesn't occur again before the line ends.
+    fn scan_delimited<T: FromStr>(&mut self) -> Result<T, String>;
+
+    /// Returns the next char without consuming it, or `None` at end of input.
+    fn peek(&mut self) -> Option<char>;
+
+    /// Snapshots the scanner's position so a later, failed speculative parse
+    /// can be undone with `reset`.
+    fn mark(&mut self) -> Mark;
+
+    /// Restores the position captured by `mark`. For readers that don't
+    /// implement `Seek`, only the line that was current at `mark` is restored;
+    /// lines read after it are not read again.
+    fn reset(&mut self, m: Mark);
+
+    /// Deserializes a `T` from the scanner via `serde`, the way `scan` does for
+    /// types implementing `FromStr`.
+    fn scan_de<T: DeserializeOwned>(&mut self) -> Result<T, String>;
+
+    /// Like `scan_de`, but bounds the scan to end at the next occurrence of
+    /// `next`, the way `scan_to` does for `FromStr` types.
+    fn scan_de_to<'a, T: DeserializeOwned, P: Pattern<'a>>(&'a mut self, next: P) -> Result<T, String>;
+
+    /// Reads a single whitespace-delimited token, much like Java's
+    /// `Scanner::next`/`nextInt`: leading whitespace (including newlines)
+    /// is skipped, then characters are accumulated until the next whitespace
+    /// character within a line, or end of input. Unlike `scan`/`scan_to`, the
+    /// token is not bounded by the current line, and a line break on its own
+    /// does not end it: `"42\n  17"` yields `42` then `17`, while `"42\n17"`
+    /// yields the single token `"4217"`.
+    fn scan_token<T: FromStr>(&mut self) -> Result<T, String> {
+        let mut buf = String::new();
+
+        loop {
+            if !self.has_next() {
+                return Err(buf);
+            }
+            match self.next()? {
+                c if c.is_whitespace() => continue,
+                c => { buf.push(c); break; }
+            }
+        }
+
+        while self.has_next() {
+            match self.next()? {
+                c if c.is_whitespace() => break,
+                c => buf.push(c),
+            }
+        }
+
+        FromStr::from_str(&buf).map_err(|_| buf)
+    }
+
+    /// Returns an iterator that yields successive `T`s delimited by `delim`,
+    /// with the final (possibly unterminated) token yielded last.
+    ///
+    /// ```ignore
+    /// let nums: Vec<u32> = scanner.tokens(",").collect::<Result<_, _>>().unwrap();
+    /// ```
+    fn tokens<'a, T: FromStr, P: Pattern<'a> + Clone>(&'a mut self, delim: P) -> Tokens<'a, Self, T, P>
+        where Self: Sized
+    {
+        Tokens {
+            scanner: self,
+            delim: delim,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Repeatedly scans `T`s separated by `delim` until input is exhausted,
+    /// collecting them into `C`, e.g. `scanner.scan_seq::<u32, Vec<_>, _>(",")`
+    /// for `"1,2,3,4"`. Short-circuits on the first element that fails to parse.
+    fn scan_seq<'a, T: FromStr, C: FromIterator<T>, P: Pattern<'a> + Clone>(&'a mut self, delim: P) -> Result<C, String>
+        where Self: Sized
+    {
+        self.tokens::<T, P>(delim).collect()
+    }
+}
+
+/// Iterator over the `T`-typed tokens of a `Scanner`, as produced by `Scanner::tokens`.
+pub struct Tokens<'a, S: 'a + ?Sized, T, P> {
+    scanner: &'a mut S,
+    delim: P,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, S: Scanner + ?Sized, T: FromStr, P: Pattern<'a> + Clone> Iterator for Tokens<'a, S, T, P> {
+    type Item = Result<T, String>;
+
+    fn next(&mut self) -> Option<Result<T, String>> {
+        if !self.scanner.has_next() {
+            return None;
+        }
+
+        // `contains` tells "delimiter absent" (fall back to `scan`, which
+        // reads the final, unterminated token) apart from "delimiter present
+        // but the token before it didn't parse" (propagate the error as-is,
+        // rather than silently resuming on the next element).
+        if self.scanner.contains(self.delim.clone()) {
+            Some(self.scanner.scan_to::<T, P>(self.delim.clone()))
+        } else {
+            Some(self.scanner.scan::<T>())
+        }
+    }
+}
+
+/// Types that can be captured as an owned default delimiter by `Scanner::use_delimiter`.
+pub trait Delimiter {
+    fn into_delimiter(self) -> String;
+}
+
+impl Delimiter for char {
+    fn into_delimiter(self) -> String { self.to_string() }
+}
+
+impl<'a> Delimiter for &'a str {
+    fn into_delimiter(self) -> String { self.to_owned() }
+}
+
+impl Delimiter for String {
+    fn into_delimiter(self) -> String { self }
 }
 
 pub fn scan_str<'a>(input: &'a str) -> impl Scanner + 'a {
@@ -72,6 +208,48 @@ pub struct LineReadScanner<R: Read> {
     reader: BufReader<R>,
     cur_line: Option<String>,
     cur_pos: usize,
+    delimiter: Option<String>,
+}
+
+/// A snapshot of a `Scanner`'s position, taken by `Scanner::mark` and restored
+/// by `Scanner::reset`.
+pub struct Mark {
+    cur_line: Option<String>,
+    cur_pos: usize,
+    line_end: Option<u64>,
+}
+
+// Rewinds the underlying reader on `reset`, when that's possible. The default
+// impl below is a no-op: without `Seek`, a `reset` that crosses a line
+// boundary can only restore the buffered line, not re-acquire lines already
+// consumed from the stream.
+trait Rewind {
+    // The stream position just after the most recently read line, if the
+    // reader supports `Seek`, or `None` otherwise. Queried only from `mark`,
+    // rather than cached on every `read_line`: `BufReader::seek` throws away
+    // its read-ahead buffer as a side effect, so calling it unconditionally on
+    // every line would turn ordinary buffered reading into a seek-per-line for
+    // every `scan_file`/`scan_file_from_path` caller, even when `mark`/`reset`
+    // are never used.
+    fn stream_pos(&mut self) -> Option<u64>;
+    fn rewind_to(&mut self, m: &Mark);
+}
+
+impl<R: Read> Rewind for LineReadScanner<R> {
+    default fn stream_pos(&mut self) -> Option<u64> { None }
+    default fn rewind_to(&mut self, _m: &Mark) {}
+}
+
+impl<R: Read + Seek> Rewind for LineReadScanner<R> {
+    fn stream_pos(&mut self) -> Option<u64> {
+        self.reader.seek(SeekFrom::Current(0)).ok()
+    }
+
+    fn rewind_to(&mut self, m: &Mark) {
+        if let Some(pos) = m.line_end {
+            let _ = self.reader.seek(SeekFrom::Start(pos));
+        }
+    }
 }
 
 impl<R: Read> LineReadScanner<R> {
@@ -80,6 +258,7 @@ impl<R: Read> LineReadScanner<R> {
             reader: BufReader::new(reader),
             cur_line: None,
             cur_pos: 0,
+            delimiter: None,
         }
     }
 
@@ -92,7 +271,7 @@ impl<R: Read> LineReadScanner<R> {
                 if &s[s.len() - 1..] == "\n" {
                     self.cur_line = Some(s[..s.len() - 1].to_owned());
                 } else {
-                    self.cur_line = Some(s.to_owned());                    
+                    self.cur_line = Some(s.to_owned());
                 }
             }
         }
@@ -141,10 +320,17 @@ impl<R: Read> Scanner for LineReadScanner<R> {
                 Ok(s.len())
             } else {
                 Err(rest.to_owned())
-            }            
+            }
         })
     }
 
+    fn contains<'a, P: Pattern<'a>>(&'a mut self, next: P) -> bool {
+        self.with_cur_line(|line, cur_pos| {
+            let rest = &line[*cur_pos..];
+            Ok(rest.match_indices(next).next().is_some())
+        }).unwrap_or(false)
+    }
+
     fn has_next(&mut self) -> bool {
         self.advance_line();
         self.cur_line.is_some()
@@ -189,14 +375,29 @@ impl<R: Read> Scanner for LineReadScanner<R> {
                 }
                 None => {
                     return Err(rest.to_owned());
-                    // The below code gives the correct behaviour for scan_to_or_end
-                    // let end = min(result.len(), rest.len());
-                    // copy_str(rest, result, end);
-                    // *cur_pos = line.len();
                 }
             }
             Ok(result.len())
-        })        
+        })
+    }
+
+    fn scan_str_to_or_end<'a, P: Pattern<'a>>(&'a mut self, result: &mut str, next: P) -> Result<usize, String> {
+        self.with_cur_line(|line, cur_pos| {
+            let rest = &line[*cur_pos..];
+            match rest.match_indices(next).next() {
+                Some((index, s)) => {
+                    let end = min(result.len(), index);
+                    copy_str(rest, result, end);
+                    *cur_pos += index + s.len();
+                }
+                None => {
+                    let end = min(result.len(), rest.len());
+                    copy_str(rest, result, end);
+                    *cur_pos = line.len();
+                }
+            }
+            Ok(result.len())
+        })
     }
 
     fn scan<T: FromStr>(&mut self) -> Result<T, String> {
@@ -219,13 +420,269 @@ impl<R: Read> Scanner for LineReadScanner<R> {
                 }
                 None => {
                     Err(rest.to_owned())
-                    // The below code gives the correct behaviour for scan_to_or_end
-                    // *cur_pos = line.len();
-                    // LineReadScanner::<R>::scan_internal(rest)
                 }
             }
         })
     }
+
+    fn scan_to_or_end<'a, T: FromStr, P: Pattern<'a>>(&'a mut self, next: P) -> Result<T, String> {
+        self.with_cur_line(|line, cur_pos| {
+            let rest = &line[*cur_pos..];
+            match rest.match_indices(next).next() {
+                Some((i, s)) => {
+                    *cur_pos += i + s.len();
+                    LineReadScanner::<R>::scan_internal(&rest[..i])
+                }
+                None => {
+                    *cur_pos = line.len();
+                    LineReadScanner::<R>::scan_internal(rest)
+                }
+            }
+        })
+    }
+
+    fn use_delimiter<D: Delimiter>(&mut self, delim: D) {
+        self.delimiter = Some(delim.into_delimiter());
+    }
+
+    fn scan_delimited<T: FromStr>(&mut self) -> Result<T, String> {
+        match self.delimiter {
+            Some(ref delim) => {
+                let delim = delim.clone();
+                self.scan_to_or_end(delim.as_str())
+            }
+            None => self.scan(),
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.advance_line();
+        self.cur_line.as_ref().and_then(|line| line[self.cur_pos..].chars().next())
+    }
+
+    fn mark(&mut self) -> Mark {
+        Mark {
+            cur_line: self.cur_line.clone(),
+            cur_pos: self.cur_pos,
+            line_end: self.stream_pos(),
+        }
+    }
+
+    fn reset(&mut self, m: Mark) {
+        self.rewind_to(&m);
+        self.cur_line = m.cur_line;
+        self.cur_pos = m.cur_pos;
+    }
+
+    fn scan_de<T: DeserializeOwned>(&mut self) -> Result<T, String> {
+        T::deserialize(ScannerDeserializer::new(self)).map_err(|e| e.0)
+    }
+
+    fn scan_de_to<'a, T: DeserializeOwned, P: Pattern<'a>>(&'a mut self, next: P) -> Result<T, String> {
+        let token = self.with_cur_line(|line, cur_pos| {
+            let rest = &line[*cur_pos..];
+            match rest.match_indices(next).next() {
+                Some((i, s)) => {
+                    *cur_pos += i + s.len();
+                    Ok(rest[..i].to_owned())
+                }
+                None => Err(rest.to_owned()),
+            }
+        })?;
+
+        let mut sub_scanner = LineReadScanner::new(token.as_bytes());
+        T::deserialize(ScannerDeserializer::new(&mut sub_scanner)).map_err(|e| e.0)
+    }
+}
+
+/// A `serde::Deserializer` that pulls its input straight from a `LineReadScanner`,
+/// so any `#[derive(Deserialize)]` type can be read with `scan_de`/`scan_de_to`.
+///
+/// At the top level, a scalar is read with `Scanner::scan_token`, which may
+/// cross line boundaries like Java Scanner does. Once inside a sequence, map,
+/// or struct, though, values are scanned bounded to the current line instead,
+/// so that e.g. deserializing a two-field struct from `"x=1\ny=2\n"` can't have
+/// the scan for `x`'s value run on into `y`'s line: a sequence inside a map or
+/// struct is whitespace-separated tokens on the current line (a top-level
+/// sequence goes on to the following lines until input runs out), and maps
+/// and structs are one `key=value` pair per line.
+pub struct ScannerDeserializer<'a, R: Read + 'a> {
+    scanner: &'a mut LineReadScanner<R>,
+    // Set by `reborrow`, i.e. whenever this deserializer is reading a sequence
+    // element or a map/struct field value, so its token scan can't run past
+    // the current line into the next field.
+    bounded: bool,
+}
+
+/// The error type produced by `ScannerDeserializer`. Wraps the same plain
+/// `String` errors used throughout the rest of the `Scanner` API.
+#[derive(Debug)]
+pub struct ScanDeError(String);
+
+impl fmt::Display for ScanDeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl ::std::error::Error for ScanDeError {
+    fn description(&self) -> &str {
+        &self.0
+    }
+}
+
+impl de::Error for ScanDeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        ScanDeError(msg.to_string())
+    }
+}
+
+impl From<String> for ScanDeError {
+    fn from(s: String) -> Self {
+        ScanDeError(s)
+    }
+}
+
+impl<'a, R: Read> ScannerDeserializer<'a, R> {
+    fn new(scanner: &'a mut LineReadScanner<R>) -> Self {
+        ScannerDeserializer { scanner: scanner, bounded: false }
+    }
+
+    fn scan_token<T: FromStr>(&mut self) -> Result<T, ScanDeError> {
+        if self.bounded {
+            self.scanner.scan_to_or_end(char::is_whitespace).map_err(ScanDeError)
+        } else {
+            self.scanner.scan_token().map_err(ScanDeError)
+        }
+    }
+
+    fn reborrow(&mut self) -> ScannerDeserializer<R> {
+        ScannerDeserializer { scanner: &mut *self.scanner, bounded: true }
+    }
+
+    /// Whether there's more to scan. When `bounded`, this must not go through
+    /// `Scanner::has_next`, since that calls `advance_line` and would
+    /// silently load whatever line comes next -- exactly the line-crossing
+    /// bug `bounded` exists to prevent.
+    fn has_next(&mut self) -> bool {
+        if self.bounded {
+            match self.scanner.cur_line {
+                Some(ref line) => self.scanner.cur_pos < line.len(),
+                None => false,
+            }
+        } else {
+            self.scanner.has_next()
+        }
+    }
+}
+
+macro_rules! deserialize_scanned {
+    ($deserialize_method:ident, $visit_method:ident) => {
+        fn $deserialize_method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScanDeError> {
+            visitor.$visit_method(self.scan_token()?)
+        }
+    }
+}
+
+impl<'de, 'a, R: Read> de::Deserializer<'de> for ScannerDeserializer<'a, R> {
+    type Error = ScanDeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, ScanDeError> {
+        Err(ScanDeError("scan_de cannot infer a type; call a typed deserialize_* \
+                         method instead of deserialize_any".to_owned()))
+    }
+
+    deserialize_scanned!(deserialize_bool, visit_bool);
+    deserialize_scanned!(deserialize_i8, visit_i8);
+    deserialize_scanned!(deserialize_i16, visit_i16);
+    deserialize_scanned!(deserialize_i32, visit_i32);
+    deserialize_scanned!(deserialize_i64, visit_i64);
+    deserialize_scanned!(deserialize_u8, visit_u8);
+    deserialize_scanned!(deserialize_u16, visit_u16);
+    deserialize_scanned!(deserialize_u32, visit_u32);
+    deserialize_scanned!(deserialize_u64, visit_u64);
+    deserialize_scanned!(deserialize_f32, visit_f32);
+    deserialize_scanned!(deserialize_f64, visit_f64);
+    deserialize_scanned!(deserialize_char, visit_char);
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScanDeError> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScanDeError> {
+        visitor.visit_string(self.scan_token()?)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScanDeError> {
+        // There is no textual "null" marker in a plain scan; every field is present.
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScanDeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScanDeError> {
+        struct TokenSeq<'a, 'b: 'a, R: Read + 'b>(&'a mut ScannerDeserializer<'b, R>);
+
+        impl<'de, 'a, 'b, R: Read> de::SeqAccess<'de> for TokenSeq<'a, 'b, R> {
+            type Error = ScanDeError;
+
+            fn next_element_seed<S: de::DeserializeSeed<'de>>(&mut self, seed: S)
+                -> Result<Option<S::Value>, ScanDeError>
+            {
+                if !self.0.has_next() {
+                    return Ok(None);
+                }
+                seed.deserialize(self.0.reborrow()).map(Some)
+            }
+        }
+
+        let mut this = self;
+        visitor.visit_seq(TokenSeq(&mut this))
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScanDeError> {
+        struct FieldMap<'a, 'b: 'a, R: Read + 'b>(&'a mut ScannerDeserializer<'b, R>);
+
+        impl<'de, 'a, 'b, R: Read> de::MapAccess<'de> for FieldMap<'a, 'b, R> {
+            type Error = ScanDeError;
+
+            fn next_key_seed<S: de::DeserializeSeed<'de>>(&mut self, seed: S)
+                -> Result<Option<S::Value>, ScanDeError>
+            {
+                if !self.0.has_next() {
+                    return Ok(None);
+                }
+                let key: String = self.0.scanner.scan_to("=").map_err(ScanDeError)?;
+                seed.deserialize(ScannerDeserializer::new(&mut LineReadScanner::new(key.as_bytes())))
+                    .map(Some)
+            }
+
+            fn next_value_seed<S: de::DeserializeSeed<'de>>(&mut self, seed: S)
+                -> Result<S::Value, ScanDeError>
+            {
+                seed.deserialize(self.0.reborrow())
+            }
+        }
+
+        let mut this = self;
+        visitor.visit_map(FieldMap(&mut this))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, _name: &'static str, _fields: &'static [&'static str],
+                                            visitor: V) -> Result<V::Value, ScanDeError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ScanDeError> {
+        self.deserialize_str(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bytes byte_buf unit_struct newtype_struct tuple tuple_struct
+        enum ignored_any
+    }
 }
 
 // `from` and `to` must not overlap.
@@ -241,7 +698,9 @@ fn copy_str(from: &str, to: &mut str, count: usize) {
 
 #[cfg(test)]
 mod test {
-    use super::{scan_str, Scanner};
+    use std::collections::HashSet;
+    use std::io::Cursor;
+    use super::{scan_str, LineReadScanner, Scanner};
 
     // TODO to test
     // scan and scan_to with a few non-String types.
@@ -288,6 +747,94 @@ mod test {
         assert!(!ss.has_next());
     }
 
+    #[test]
+    fn test_tokens() {
+        let mut ss = scan_str("1,2,3,4");
+        let nums = ss.tokens::<u32, _>(",").collect::<Result<Vec<_>, _>>().unwrap();
+        assert!(nums == vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_tokens_mid_sequence_parse_error() {
+        let mut ss = scan_str("1,x,3");
+        assert!(ss.tokens::<u32, _>(",").collect::<Result<Vec<_>, _>>().is_err());
+    }
+
+    #[test]
+    fn test_scan_token_crosses_lines() {
+        let mut ss = scan_str("42\n  17");
+        assert!(ss.scan_token().unwrap(): u32 == 42);
+        assert!(ss.scan_token().unwrap(): u32 == 17);
+        assert!(!ss.has_next());
+    }
+
+    #[test]
+    fn test_scan_to_or_end() {
+        let mut ss = scan_str("Hello, world!");
+        assert!(ss.scan_to_or_end(",").unwrap(): String == "Hello");
+        ss.next().unwrap();
+        assert!(ss.scan_to_or_end(",").unwrap(): String == "world!");
+    }
+
+    #[test]
+    fn test_use_delimiter() {
+        let mut ss = scan_str("1,2,3");
+        ss.use_delimiter(",");
+        assert!(ss.scan_delimited().unwrap(): u32 == 1);
+        assert!(ss.scan_delimited().unwrap(): u32 == 2);
+        assert!(ss.scan_delimited().unwrap(): u32 == 3);
+    }
+
+    #[test]
+    fn test_peek_and_reset() {
+        let mut ss = scan_str("Hello, world!");
+
+        assert!(ss.peek() == Some('H'));
+        assert!(ss.peek() == Some('H'));
+
+        let m = ss.mark();
+        assert!(ss.scan_to(",").unwrap(): String == "Hello");
+        ss.reset(m);
+        assert!(ss.scan_to(",").unwrap(): String == "Hello");
+    }
+
+    #[test]
+    fn test_mark_reset_across_line_boundary() {
+        // scan_str's &[u8] reader doesn't implement Seek, so it never
+        // exercises the real Read + Seek rewind path -- use a Cursor instead.
+        let mut ss = LineReadScanner::new(Cursor::new(b"Hello\nworld\n".to_vec()));
+
+        assert!(ss.next().unwrap() == 'H');
+        let m = ss.mark();
+
+        assert!(ss.scan().unwrap(): String == "ello");
+        assert!(ss.scan().unwrap(): String == "world");
+        assert!(!ss.has_next());
+
+        ss.reset(m);
+        assert!(ss.next().unwrap() == 'e');
+        assert!(ss.scan().unwrap(): String == "llo");
+        assert!(ss.scan().unwrap(): String == "world");
+    }
+
+    #[test]
+    fn test_scan_seq() {
+        let mut ss = scan_str("1,2,3,4");
+        let nums: Vec<u32> = ss.scan_seq(",").unwrap();
+        assert!(nums == vec![1, 2, 3, 4]);
+
+        let mut ss = scan_str("1 1 2 3");
+        let nums: HashSet<u32> = ss.scan_seq(char::is_whitespace).unwrap();
+        assert!(nums == [1, 2, 3].iter().cloned().collect());
+    }
+
+    #[test]
+    fn test_scan_seq_mid_sequence_parse_error() {
+        let mut ss = scan_str("1,x,3");
+        let result: Result<Vec<u32>, String> = ss.scan_seq(",");
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_expect() {
         let mut ss = scan_str("Hello, world!");
@@ -297,4 +844,30 @@ mod test {
         ss.expect(' ').unwrap();
         assert!(ss.next() == Ok('w'));
     }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Point {
+        x: u32,
+        y: u32,
+    }
+
+    #[test]
+    fn test_scan_de_struct_one_field_per_line() {
+        let mut ss = scan_str("x=1\ny=2\n");
+        let p: Point = ss.scan_de().unwrap();
+        assert!(p == Point { x: 1, y: 2 });
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct WithSeq {
+        nums: Vec<u32>,
+        y: u32,
+    }
+
+    #[test]
+    fn test_scan_de_seq_field_stays_on_its_line() {
+        let mut ss = scan_str("nums=1 2 3\ny=4\n");
+        let w: WithSeq = ss.scan_de().unwrap();
+        assert!(w == WithSeq { nums: vec![1, 2, 3], y: 4 });
+    }
 }